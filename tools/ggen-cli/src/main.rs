@@ -2,10 +2,19 @@ use clap::{Parser, Subcommand};
 use anyhow::{Context, Result};
 use oxigraph::store::Store;
 use oxigraph::sparql::{Query, QueryResults};
-use oxigraph::model::Term;
+use oxigraph::io::RdfFormat;
+use oxigraph::model::{Subject, Term};
+use notify::{EventKind, RecursiveMode, Watcher};
+use rayon::prelude::*;
 use serde::Serialize;
+use similar::TextDiff;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 use tera::{Tera, Context as TeraContext};
 use walkdir::WalkDir;
 
@@ -45,6 +54,25 @@ enum Commands {
         /// Verbose output
         #[arg(long, short)]
         verbose: bool,
+
+        /// Fail if the generated output would differ from what's already on disk
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Recompile automatically whenever the ontology changes
+    Watch {
+        /// Source ontology directory
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Target output directory
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Verbose output
+        #[arg(long, short)]
+        verbose: bool,
     },
 
     /// Display version
@@ -53,6 +81,7 @@ enum Commands {
 
 #[derive(Debug, Serialize)]
 struct OntologyClass {
+    iri: String,
     name: String,
     comment: String,
     properties: Vec<Property>,
@@ -60,43 +89,87 @@ struct OntologyClass {
 
 #[derive(Debug, Serialize)]
 struct Property {
+    iri: String,
     name: String,
     comment: String,
     rust_type: String,
     python_type: String,
     typescript_type: String,
+    range_iri: String,
     optional: bool,
 }
 
+/// A class enumerated via `owl:oneOf`, so templates can emit a real Rust
+/// enum / TypeScript string union instead of a bare string field. Classes
+/// whose individuals are only asserted with `rdf:type` (no `owl:oneOf`) are
+/// not extracted as enumerations.
+#[derive(Debug, Serialize)]
+struct Enumeration {
+    name: String,
+    comment: String,
+    values: Vec<String>,
+}
+
 fn load_ontology(ontology_dir: &Path, verbose: bool) -> Result<Store> {
     let store = Store::new()?;
+    load_ontology_into(&store, ontology_dir, verbose)?;
+    Ok(store)
+}
+
+/// Maps a file extension to the oxigraph parser that reads it. Named-graph
+/// formats (N-Quads, TriG) are returned as-is: oxigraph's reader already
+/// assigns their triples to the graphs named in the file rather than the
+/// default graph, so no extra handling is needed for those beyond picking
+/// the right format here.
+fn rdf_format_for_path(path: &Path) -> Option<RdfFormat> {
+    match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+        "ttl" => Some(RdfFormat::Turtle),
+        "nt" => Some(RdfFormat::NTriples),
+        "nq" => Some(RdfFormat::NQuads),
+        "trig" => Some(RdfFormat::TriG),
+        "rdf" | "owl" => Some(RdfFormat::RdfXml),
+        _ => None,
+    }
+}
+
+fn is_ontology_file(path: &Path) -> bool {
+    rdf_format_for_path(path).is_some()
+}
 
+/// Loads every recognized ontology file under `ontology_dir` into an
+/// already-open `Store`. Split out from `load_ontology` so long-lived
+/// callers (e.g. `watch`) can clear and reload the same store across
+/// rebuilds instead of paying the cost of opening a fresh one each time.
+fn load_ontology_into(store: &Store, ontology_dir: &Path, verbose: bool) -> Result<()> {
     if verbose {
         println!("📖 Loading ontologies from: {}", ontology_dir.display());
     }
 
-    // Find all .ttl files in the ontology directory
+    // Find every file whose extension maps to a known RDF serialization.
     for entry in WalkDir::new(ontology_dir)
         .follow_links(true)
         .into_iter()
         .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "ttl"))
+        .filter(|e| e.file_type().is_file())
     {
         let path = entry.path();
+        let Some(format) = rdf_format_for_path(path) else {
+            continue;
+        };
+
         if verbose {
-            println!("  - Loading: {}", path.display());
+            println!("  - Loading ({}): {}", format.name(), path.display());
         }
 
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
 
-        store.load_from_reader(
-            oxigraph::io::RdfFormat::Turtle,
-            content.as_bytes(),
-        )?;
+        store
+            .load_from_reader(format, content.as_bytes())
+            .with_context(|| format!("Failed to parse {} as {}", path.display(), format.name()))?;
     }
 
-    Ok(store)
+    Ok(())
 }
 
 fn extract_classes(store: &Store, verbose: bool) -> Result<Vec<OntologyClass>> {
@@ -158,6 +231,7 @@ fn extract_classes(store: &Store, verbose: bool) -> Result<Vec<OntologyClass>> {
                 let properties = extract_properties(store, class_iri, verbose)?;
 
                 classes.push(OntologyClass {
+                    iri: class_iri.to_string(),
                     name: class_name.to_string(),
                     comment,
                     properties,
@@ -169,6 +243,90 @@ fn extract_classes(store: &Store, verbose: bool) -> Result<Vec<OntologyClass>> {
     Ok(classes)
 }
 
+/// Cardinality bounds read off a property's `owl:Restriction` axioms (an
+/// exact `owl:cardinality N` counts as both `min` and `max` equal to `N`).
+#[derive(Default, Clone, Copy)]
+struct Cardinality {
+    min: Option<i64>,
+    max: Option<i64>,
+}
+
+/// Reads every `owl:Restriction` that `class_iri` is a (possibly indirect)
+/// `rdfs:subClassOf`, keyed by the restricted property's IRI. Also treats
+/// any `owl:FunctionalProperty` as an implicit `maxCardinality 1` when no
+/// explicit restriction overrides it.
+fn extract_cardinalities(store: &Store, class_iri: &str) -> Result<HashMap<String, Cardinality>> {
+    let query_str = format!(
+        r#"
+        PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+        PREFIX owl: <http://www.w3.org/2002/07/owl#>
+
+        SELECT ?property ?min ?max ?cardinality ?functional
+        WHERE {{
+            {{
+                <{class}> rdfs:subClassOf* ?restriction .
+                ?restriction a owl:Restriction ;
+                    owl:onProperty ?property .
+                OPTIONAL {{ ?restriction owl:minCardinality ?min }}
+                OPTIONAL {{ ?restriction owl:maxCardinality ?max }}
+                OPTIONAL {{ ?restriction owl:cardinality ?cardinality }}
+            }} UNION {{
+                ?property rdfs:domain <{class}> ;
+                    a owl:FunctionalProperty .
+                BIND(1 AS ?functional)
+            }}
+        }}
+    "#,
+        class = class_iri
+    );
+
+    let query = Query::parse(&query_str, None)?;
+    let results = store.query(query)?;
+    let mut cardinalities: HashMap<String, Cardinality> = HashMap::new();
+
+    if let QueryResults::Solutions(solutions) = results {
+        for solution in solutions {
+            let solution = solution?;
+
+            let Some(prop_term) = solution.get("property") else {
+                continue;
+            };
+            let prop_iri = match prop_term {
+                Term::NamedNode(n) => n.as_str().to_string(),
+                _ => continue,
+            };
+
+            let as_i64 = |name: &str| -> Option<i64> {
+                solution.get(name).and_then(|v| {
+                    // Literal terms render as `"2"^^<...xsd#integer>`; take just
+                    // the quoted value, ignoring the datatype suffix.
+                    let raw = v.to_string();
+                    let raw = raw.trim_start_matches('"');
+                    let value = &raw[..raw.find('"').unwrap_or(raw.len())];
+                    value.parse().ok()
+                })
+            };
+
+            let entry = cardinalities.entry(prop_iri).or_default();
+            if let Some(exact) = as_i64("cardinality") {
+                entry.min = Some(exact);
+                entry.max = Some(exact);
+            }
+            if let Some(min) = as_i64("min") {
+                entry.min = Some(min);
+            }
+            if let Some(max) = as_i64("max") {
+                entry.max = Some(max);
+            }
+            if solution.get("functional").is_some() && entry.max.is_none() {
+                entry.max = Some(1);
+            }
+        }
+    }
+
+    Ok(cardinalities)
+}
+
 fn extract_properties(store: &Store, class_iri: &str, _verbose: bool) -> Result<Vec<Property>> {
     let query_str = format!(r#"
         PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
@@ -188,6 +346,7 @@ fn extract_properties(store: &Store, class_iri: &str, _verbose: bool) -> Result<
     let query = Query::parse(&query_str, None)?;
     let results = store.query(query)?;
     let mut properties = Vec::new();
+    let cardinalities = extract_cardinalities(store, class_iri)?;
 
     if let QueryResults::Solutions(solutions) = results {
         for solution in solutions {
@@ -200,6 +359,7 @@ fn extract_properties(store: &Store, class_iri: &str, _verbose: bool) -> Result<
                     .last()
                     .unwrap_or("unknown")
                     .trim_matches('>');
+                let prop_iri = prop_uri.trim_start_matches('<').trim_end_matches('>').to_string();
 
                 let comment = solution.get("comment")
                     .map(|v| v.to_string().trim_matches('"').to_string())
@@ -208,17 +368,40 @@ fn extract_properties(store: &Store, class_iri: &str, _verbose: bool) -> Result<
                 let range = solution.get("range")
                     .map(|v| v.to_string())
                     .unwrap_or_else(|| "xsd:string".to_string());
+                let range_iri = range.trim_start_matches('<').trim_end_matches('>').to_string();
 
                 // Map XSD types to target language types
                 let (rust_type, python_type, typescript_type) = map_xsd_type(&range);
 
+                let cardinality = cardinalities.get(&prop_iri).copied().unwrap_or_default();
+                let optional = cardinality.min == Some(0);
+                let is_list = cardinality.max.is_some_and(|max| max > 1);
+
+                let (rust_type, python_type, typescript_type) = if is_list {
+                    (
+                        format!("Vec<{}>", rust_type),
+                        format!("List[{}]", python_type),
+                        format!("{}[]", typescript_type),
+                    )
+                } else if optional {
+                    (
+                        format!("Option<{}>", rust_type),
+                        format!("Optional[{}]", python_type),
+                        format!("{} | null", typescript_type),
+                    )
+                } else {
+                    (rust_type, python_type, typescript_type)
+                };
+
                 properties.push(Property {
+                    iri: prop_iri,
                     name: prop_name.to_string(),
                     comment,
                     rust_type,
                     python_type,
                     typescript_type,
-                    optional: false,
+                    range_iri,
+                    optional,
                 });
             }
         }
@@ -227,6 +410,107 @@ fn extract_properties(store: &Store, class_iri: &str, _verbose: bool) -> Result<
     Ok(properties)
 }
 
+/// Finds every class declared via `owl:oneOf` (e.g. `:Color a owl:Class ;
+/// owl:oneOf (:Red :Green :Blue)`) and resolves the RDF list into the local
+/// names of its members, so each becomes a usable enum variant name.
+fn extract_enumerations(store: &Store, verbose: bool) -> Result<Vec<Enumeration>> {
+    if verbose {
+        println!("\n🔢 Extracting enumerations from ontology...");
+    }
+
+    let query_str = r#"
+        PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+        PREFIX owl: <http://www.w3.org/2002/07/owl#>
+
+        SELECT DISTINCT ?class ?comment
+        WHERE {
+            ?class owl:oneOf ?list .
+            OPTIONAL { ?class rdfs:comment ?comment }
+        }
+        ORDER BY ?class
+    "#;
+
+    let query = Query::parse(query_str, None)?;
+    let results = store.query(query)?;
+    let mut enumerations = Vec::new();
+
+    if let QueryResults::Solutions(solutions) = results {
+        for solution in solutions {
+            let solution = solution?;
+
+            let Some(Term::NamedNode(class_node)) = solution.get("class") else {
+                continue;
+            };
+            let class_iri = class_node.as_str();
+
+            let name = class_iri.split(&['#', '/'][..]).last().unwrap_or("Unknown");
+
+            let comment = solution
+                .get("comment")
+                .map(|v| v.to_string().trim_matches('"').to_string())
+                .unwrap_or_default();
+
+            let values = extract_one_of_members(store, class_iri)?;
+            if values.is_empty() {
+                continue;
+            }
+
+            if verbose {
+                println!("  ✓ Found enumeration: {} ({} value(s))", name, values.len());
+            }
+
+            enumerations.push(Enumeration {
+                name: name.to_string(),
+                comment,
+                values,
+            });
+        }
+    }
+
+    Ok(enumerations)
+}
+
+/// Walks the `rdf:first`/`rdf:rest*` list bound to `class_iri`'s
+/// `owl:oneOf` and returns the local names of its members, sorted for
+/// deterministic output.
+fn extract_one_of_members(store: &Store, class_iri: &str) -> Result<Vec<String>> {
+    let query_str = format!(
+        r#"
+        PREFIX rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>
+        PREFIX owl: <http://www.w3.org/2002/07/owl#>
+
+        SELECT DISTINCT ?member
+        WHERE {{
+            <{}> owl:oneOf ?list .
+            ?list rdf:rest*/rdf:first ?member .
+        }}
+    "#,
+        class_iri
+    );
+
+    let query = Query::parse(&query_str, None)?;
+    let results = store.query(query)?;
+    let mut members = Vec::new();
+
+    if let QueryResults::Solutions(solutions) = results {
+        for solution in solutions {
+            let solution = solution?;
+            if let Some(member_term) = solution.get("member") {
+                let member_iri = member_term.to_string();
+                let name = member_iri
+                    .split(&['#', '/'][..])
+                    .last()
+                    .unwrap_or("Unknown")
+                    .trim_matches('>');
+                members.push(name.to_string());
+            }
+        }
+    }
+
+    members.sort();
+    Ok(members)
+}
+
 fn map_xsd_type(xsd_type: &str) -> (String, String, String) {
     if xsd_type.contains("string") {
         ("String".to_string(), "str".to_string(), "string".to_string())
@@ -249,30 +533,597 @@ fn map_xsd_type(xsd_type: &str) -> (String, String, String) {
     }
 }
 
-fn render_templates(
+/// A triple with blank nodes kept as their raw store-local identifiers, i.e.
+/// not yet canonicalized. Used as the input to blank-node canonicalization.
+#[derive(Clone)]
+struct RawTriple {
+    subject: NodeKey,
+    predicate: String,
+    object: NodeKey,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum NodeKey {
+    Named(String),
+    Blank(String),
+    Literal(String),
+}
+
+/// Result of comparing two ontology graphs for semantic (isomorphic) equality.
+struct GraphDiff {
+    isomorphic: bool,
+    added: Vec<(String, String, String)>,
+    removed: Vec<(String, String, String)>,
+}
+
+fn collect_graph_triples(store: &Store) -> Result<Vec<RawTriple>> {
+    let mut triples = Vec::new();
+
+    for quad in store.iter() {
+        let quad = quad?;
+
+        let subject = match &quad.subject {
+            Subject::NamedNode(n) => NodeKey::Named(n.as_str().to_string()),
+            Subject::BlankNode(b) => NodeKey::Blank(b.as_str().to_string()),
+            other => NodeKey::Named(other.to_string()),
+        };
+
+        let object = match &quad.object {
+            Term::NamedNode(n) => NodeKey::Named(n.as_str().to_string()),
+            Term::BlankNode(b) => NodeKey::Blank(b.as_str().to_string()),
+            Term::Literal(l) => NodeKey::Literal(l.to_string()),
+            other => NodeKey::Literal(other.to_string()),
+        };
+
+        triples.push(RawTriple {
+            subject,
+            predicate: quad.predicate.as_str().to_string(),
+            object,
+        });
+    }
+
+    Ok(triples)
+}
+
+fn hash_signature(sig: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    sig.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn count_partitions(hashes: &HashMap<String, u64>) -> usize {
+    hashes.values().collect::<HashSet<_>>().len()
+}
+
+/// Builds the sorted signature of a blank node's incident triples. On the
+/// first pass (`neighbor_hashes == None`) every *other* blank node is folded
+/// into the fixed placeholder `BLANK`; on later passes it is replaced with
+/// that neighbor's current refined hash, so the signature grows more
+/// discriminating each round.
+fn incident_signature(
+    id: &str,
+    incident: &HashMap<String, Vec<usize>>,
+    triples: &[RawTriple],
+    neighbor_hashes: Option<&HashMap<String, u64>>,
+) -> String {
+    let render = |k: &NodeKey| -> String {
+        match k {
+            NodeKey::Named(s) => format!("N:{}", s),
+            NodeKey::Literal(s) => format!("L:{}", s),
+            NodeKey::Blank(bid) if bid == id => "SELF".to_string(),
+            NodeKey::Blank(bid) => match neighbor_hashes {
+                Some(h) => format!("B:{:x}", h.get(bid).copied().unwrap_or(0)),
+                None => "BLANK".to_string(),
+            },
+        }
+    };
+
+    let mut parts: Vec<String> = incident[id]
+        .iter()
+        .map(|&i| {
+            let t = &triples[i];
+            format!("{}|{}|{}", render(&t.subject), t.predicate, render(&t.object))
+        })
+        .collect();
+    parts.sort();
+    parts.join(";")
+}
+
+/// Hash-based canonical labeling of blank nodes (a Weisfeiler-Leman-style
+/// color refinement): each blank node starts out hashed by the multiset of
+/// its incident triples with other blank nodes hidden behind a placeholder,
+/// then iteratively refolds in its neighbors' hashes until the partition of
+/// hashes stops growing finer.
+fn canonical_blank_labels(triples: &[RawTriple]) -> HashMap<String, u64> {
+    let mut incident: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, t) in triples.iter().enumerate() {
+        if let NodeKey::Blank(id) = &t.subject {
+            incident.entry(id.clone()).or_default().push(i);
+        }
+        if let NodeKey::Blank(id) = &t.object {
+            incident.entry(id.clone()).or_default().push(i);
+        }
+    }
+
+    let blank_ids: Vec<String> = incident.keys().cloned().collect();
+    if blank_ids.is_empty() {
+        return HashMap::new();
+    }
+
+    let mut hashes: HashMap<String, u64> = blank_ids
+        .iter()
+        .map(|id| (id.clone(), hash_signature(&incident_signature(id, &incident, triples, None))))
+        .collect();
+    let mut partitions = count_partitions(&hashes);
+
+    // A stable partition is reached in at most |V| rounds of refinement.
+    for _ in 0..=blank_ids.len() {
+        let next: HashMap<String, u64> = blank_ids
+            .iter()
+            .map(|id| {
+                let sig = incident_signature(id, &incident, triples, Some(&hashes));
+                let combined = format!("{}|{}", hashes[id], sig);
+                (id.clone(), hash_signature(&combined))
+            })
+            .collect();
+
+        let next_partitions = count_partitions(&next);
+        hashes = next;
+        if next_partitions == partitions {
+            break;
+        }
+        partitions = next_partitions;
+    }
+
+    hashes
+}
+
+fn canon_label(hash: u64) -> String {
+    format!("_:c{:016x}", hash)
+}
+
+fn render_node(k: &NodeKey, labels: &HashMap<String, u64>, overrides: &HashMap<String, String>) -> String {
+    match k {
+        NodeKey::Named(s) => s.clone(),
+        NodeKey::Literal(s) => s.clone(),
+        NodeKey::Blank(id) => overrides
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| canon_label(labels[id])),
+    }
+}
+
+fn canonical_triples(
+    triples: &[RawTriple],
+    labels: &HashMap<String, u64>,
+    overrides: &HashMap<String, String>,
+) -> Vec<(String, String, String)> {
+    let mut rendered: Vec<_> = triples
+        .iter()
+        .map(|t| {
+            (
+                render_node(&t.subject, labels, overrides),
+                t.predicate.clone(),
+                render_node(&t.object, labels, overrides),
+            )
+        })
+        .collect();
+    rendered.sort();
+    rendered
+}
+
+/// Groups of blank node ids that refined to the same final hash. A group
+/// with more than one member means the graph could not distinguish those
+/// nodes by structure alone (e.g. a symmetric OWL restriction) - ambiguous
+/// in isolation, but resolvable when compared against a specific other graph.
+fn collision_groups(labels: &HashMap<String, u64>) -> Vec<Vec<String>> {
+    let mut by_hash: HashMap<u64, Vec<String>> = HashMap::new();
+    for (id, h) in labels {
+        by_hash.entry(*h).or_default().push(id.clone());
+    }
+    let mut groups: Vec<Vec<String>> = by_hash.into_values().filter(|v| v.len() > 1).collect();
+    for g in &mut groups {
+        g.sort();
+    }
+    groups.sort();
+    groups
+}
+
+fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    if items.is_empty() {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for i in 0..items.len() {
+        let mut rest = items.to_vec();
+        let head = rest.remove(i);
+        for mut p in permutations(&rest) {
+            p.insert(0, head.clone());
+            result.push(p);
+        }
+    }
+    result
+}
+
+/// Bounds the factorial blow-up of trying every permutation within a single
+/// colliding group of blank nodes. Real ontologies rarely have more than a
+/// handful of structurally-symmetric blank nodes at once.
+const MAX_COLLISION_GROUP: usize = 6;
+
+/// Bounds the product of permutation counts across *all* colliding groups
+/// combined. `MAX_COLLISION_GROUP` alone only caps one group's factorial
+/// blow-up; many small groups multiply just as badly (e.g. 20 groups of size
+/// 2, which a handful of duplicated restriction shapes can produce routinely,
+/// is `2^20` ≈ 1,000,000 combinations). Above this ceiling we give up and
+/// report the graphs as distinguishable rather than risk hanging.
+const MAX_TOTAL_COMBINATIONS: usize = 10_000;
+
+fn factorial(n: usize) -> usize {
+    (1..=n).product()
+}
+
+/// Renders a graph's canonical triples with each collision group's members
+/// pinned to distinct, stable `#slot` labels (ordered by the group's sorted
+/// blank-node ids) instead of collapsing them onto one shared label. This is
+/// the only form that `resolve_via_permutation`'s permuted overrides can ever
+/// match against - comparing a permuted assignment to the *plain*,
+/// slot-less canonical form (every member sharing one label) can never
+/// succeed, since the plain form has no slots to match in the first place.
+fn disambiguated_canonical_triples(
+    triples: &[RawTriple],
+    labels: &HashMap<String, u64>,
+) -> Vec<(String, String, String)> {
+    let groups = collision_groups(labels);
+    let mut overrides: HashMap<String, String> = HashMap::new();
+    for group in &groups {
+        for (slot, id) in group.iter().enumerate() {
+            overrides.insert(id.clone(), format!("{}#{}", canon_label(labels[id]), slot));
+        }
+    }
+    canonical_triples(triples, labels, &overrides)
+}
+
+/// Tries every assignment of blank-node identities within each colliding
+/// group and checks whether any of them reproduces `target` (the *other*
+/// graph's [`disambiguated_canonical_triples`]) exactly, i.e. whether the
+/// apparent mismatch was just an artifact of an unresolved automorphism
+/// rather than a real structural difference. `target` must already be in
+/// disambiguated form, or no permutation here can ever match it.
+fn resolve_via_permutation(
+    triples: &[RawTriple],
+    labels: &HashMap<String, u64>,
+    groups: &[Vec<String>],
+    target: &[(String, String, String)],
+) -> bool {
+    if groups.iter().any(|g| g.len() > MAX_COLLISION_GROUP) {
+        return false;
+    }
+
+    let total_combinations = groups
+        .iter()
+        .try_fold(1usize, |acc, g| acc.checked_mul(factorial(g.len())));
+    if !matches!(total_combinations, Some(n) if n <= MAX_TOTAL_COMBINATIONS) {
+        return false;
+    }
+
+    let group_perms: Vec<Vec<Vec<String>>> = groups.iter().map(|g| permutations(g)).collect();
+    let combos = group_perms.iter().fold(vec![Vec::new()], |acc, choices| {
+        acc.into_iter()
+            .flat_map(|prefix: Vec<Vec<String>>| {
+                choices.iter().map(move |choice| {
+                    let mut next = prefix.clone();
+                    next.push(choice.clone());
+                    next
+                })
+            })
+            .collect()
+    });
+
+    for combo in combos {
+        let mut overrides: HashMap<String, String> = HashMap::new();
+        for (group, assignment) in groups.iter().zip(combo.iter()) {
+            for (slot, original_id) in group.iter().enumerate() {
+                let slot_label = format!("{}#{}", canon_label(labels[original_id]), slot);
+                overrides.insert(assignment[slot].clone(), slot_label);
+            }
+        }
+
+        if canonical_triples(triples, labels, &overrides) == target {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Compares two ontology graphs for semantic equality, ignoring blank-node
+/// identifiers and triple order. Ground triples are compared directly;
+/// blank nodes are reconciled via hash-based canonical labeling, falling
+/// back to a bounded permutation search when refinement leaves nodes tied.
+fn compare_ontology_graphs(old: &Store, new: &Store) -> Result<GraphDiff> {
+    let old_triples = collect_graph_triples(old)?;
+    let new_triples = collect_graph_triples(new)?;
+
+    let old_labels = canonical_blank_labels(&old_triples);
+    let new_labels = canonical_blank_labels(&new_triples);
+
+    let old_canon = canonical_triples(&old_triples, &old_labels, &HashMap::new());
+    let new_canon = canonical_triples(&new_triples, &new_labels, &HashMap::new());
+
+    if old_canon == new_canon {
+        return Ok(GraphDiff { isomorphic: true, added: Vec::new(), removed: Vec::new() });
+    }
+
+    let old_collisions = collision_groups(&old_labels);
+    if !old_collisions.is_empty() {
+        let new_disambiguated = disambiguated_canonical_triples(&new_triples, &new_labels);
+        if resolve_via_permutation(&old_triples, &old_labels, &old_collisions, &new_disambiguated) {
+            return Ok(GraphDiff { isomorphic: true, added: Vec::new(), removed: Vec::new() });
+        }
+    }
+
+    let new_collisions = collision_groups(&new_labels);
+    if !new_collisions.is_empty() {
+        let old_disambiguated = disambiguated_canonical_triples(&old_triples, &old_labels);
+        if resolve_via_permutation(&new_triples, &new_labels, &new_collisions, &old_disambiguated) {
+            return Ok(GraphDiff { isomorphic: true, added: Vec::new(), removed: Vec::new() });
+        }
+    }
+
+    let old_set: HashSet<_> = old_canon.iter().cloned().collect();
+    let new_set: HashSet<_> = new_canon.iter().cloned().collect();
+    let removed = old_canon.iter().filter(|t| !new_set.contains(*t)).cloned().collect();
+    let added = new_canon.iter().filter(|t| !old_set.contains(*t)).cloned().collect();
+
+    Ok(GraphDiff { isomorphic: false, added, removed })
+}
+
+fn snapshot_path(output_dir: &Path) -> PathBuf {
+    output_dir.join(".ggen-snapshot.nq")
+}
+
+/// Records the compiled ontology graph so a later `--mode verify` run has
+/// something to compare against.
+fn write_snapshot(store: &Store, output_dir: &Path) -> Result<()> {
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create output directory: {}", output_dir.display()))?;
+
+    let path = snapshot_path(output_dir);
+    let mut writer = fs::File::create(&path)
+        .with_context(|| format!("Failed to create snapshot {}", path.display()))?;
+    store
+        .dump_to_writer(&mut writer, RdfFormat::NQuads)
+        .with_context(|| format!("Failed to write snapshot {}", path.display()))?;
+
+    Ok(())
+}
+
+fn load_snapshot(output_dir: &Path) -> Result<Option<Store>> {
+    let path = snapshot_path(output_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let store = Store::new()?;
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read snapshot {}", path.display()))?;
+    store.load_from_reader(RdfFormat::NQuads, content.as_bytes())?;
+
+    Ok(Some(store))
+}
+
+/// Implements `--mode verify`: loads the current ontology and the snapshot
+/// recorded by the last non-verify sync, and reports whether they are
+/// semantically identical. Returns `Ok(true)` when no drift was found.
+fn run_verify(ontology_dir: &Path, output_dir: &Path, verbose: bool) -> Result<bool> {
+    println!("🔎 Verifying ontology against recorded snapshot...");
+
+    let current = load_ontology(ontology_dir, verbose)?;
+    let previous = match load_snapshot(output_dir)? {
+        Some(store) => store,
+        None => {
+            println!(
+                "   ⚠️  No recorded snapshot found at {}",
+                snapshot_path(output_dir).display()
+            );
+            println!("   Run a full sync first to establish a baseline.");
+            return Ok(false);
+        }
+    };
+
+    let diff = compare_ontology_graphs(&previous, &current)?;
+
+    if diff.isomorphic {
+        println!("   ✅ No drift: current ontology is isomorphic to the recorded snapshot.");
+        Ok(true)
+    } else {
+        println!("   ❌ Drift detected:");
+        for t in &diff.removed {
+            println!("     - {} {} {}", t.0, t.1, t.2);
+        }
+        for t in &diff.added {
+            println!("     + {} {} {}", t.0, t.1, t.2);
+        }
+        println!(
+            "   {} triple(s) removed, {} triple(s) added",
+            diff.removed.len(),
+            diff.added.len()
+        );
+        Ok(false)
+    }
+}
+
+/// Collects the named (non-blank, non-literal) IRIs touched by a graph diff,
+/// i.e. the candidate domain/range/class identifiers a changed triple could
+/// have affected.
+fn diff_to_changed_iris(diff: &GraphDiff) -> HashSet<String> {
+    let mut changed = HashSet::new();
+    for t in diff.added.iter().chain(diff.removed.iter()) {
+        for s in [&t.0, &t.2] {
+            if !s.starts_with('"') && !s.starts_with("_:") {
+                changed.insert(s.clone());
+            }
+        }
+    }
+    changed
+}
+
+/// A class's resolved cardinality bounds, keyed by property IRI, plus its
+/// `owl:oneOf` members if it's an enumeration. Restriction blank nodes and
+/// `rdf:first`/`rdf:rest` list cells never appear as named IRIs in a triple
+/// diff (their subject is blank, their distinguishing object is a literal or
+/// another blank node), so comparing this resolved fingerprint directly
+/// against the two stores is how those edits are detected instead.
+fn class_restriction_fingerprint(
+    store: &Store,
+    class_iri: &str,
+) -> Result<(Vec<(String, Option<i64>, Option<i64>)>, Vec<String>)> {
+    let mut cardinalities: Vec<(String, Option<i64>, Option<i64>)> = extract_cardinalities(store, class_iri)?
+        .into_iter()
+        .map(|(property, c)| (property, c.min, c.max))
+        .collect();
+    cardinalities.sort();
+
+    let one_of = extract_one_of_members(store, class_iri)?;
+
+    Ok((cardinalities, one_of))
+}
+
+/// A class is dirty if its own IRI changed, one of its `rdfs:domain`-linked
+/// properties changed, its resolved cardinality/enum fingerprint differs
+/// between the previous and current store (catching restriction and
+/// `owl:oneOf` blank-node edits that a named-IRI diff can't see), or
+/// (transitively) a class it references via a property's range is itself
+/// dirty.
+fn compute_dirty_classes(
     classes: &[OntologyClass],
+    changed: &HashSet<String>,
+    previous: &Store,
+    current: &Store,
+) -> Result<HashSet<String>> {
+    let mut dirty: HashSet<String> = HashSet::new();
+
+    for c in classes {
+        let named_change = changed.contains(&c.iri)
+            || c.properties.iter().any(|p| changed.contains(&p.iri) || changed.contains(&p.range_iri));
+
+        let fingerprint_change = if named_change {
+            false
+        } else {
+            class_restriction_fingerprint(previous, &c.iri)? != class_restriction_fingerprint(current, &c.iri)?
+        };
+
+        if named_change || fingerprint_change {
+            dirty.insert(c.iri.clone());
+        }
+    }
+
+    loop {
+        let newly_dirty: Vec<String> = classes
+            .iter()
+            .filter(|c| !dirty.contains(&c.iri))
+            .filter(|c| c.properties.iter().any(|p| dirty.contains(&p.range_iri)))
+            .map(|c| c.iri.clone())
+            .collect();
+
+        if newly_dirty.is_empty() {
+            break;
+        }
+        dirty.extend(newly_dirty);
+    }
+
+    Ok(dirty)
+}
+
+/// Decides which `.tera` templates need re-rendering given a set of dirty
+/// class IRIs: a template that names specific classes only needs rendering
+/// when one of those classes is dirty; a template that names none (e.g. an
+/// index or mod file) is treated as global and always re-rendered.
+fn dirty_template_names(
     templates_dir: &Path,
-    output_dir: &Path,
-    dry_run: bool,
-    verbose: bool,
-) -> Result<()> {
-    if verbose {
-        println!("\n🎨 Rendering templates from: {}", templates_dir.display());
+    classes: &[OntologyClass],
+    dirty: &HashSet<String>,
+) -> Result<HashSet<String>> {
+    let mut selected = HashSet::new();
+
+    for entry in WalkDir::new(templates_dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().map_or(false, |ext| ext == "tera"))
+    {
+        let path = entry.path();
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let references_any_class = classes.iter().any(|c| source.contains(&c.name));
+        let touches_dirty_class = classes
+            .iter()
+            .any(|c| dirty.contains(&c.iri) && source.contains(&c.name));
+
+        if touches_dirty_class || !references_any_class {
+            let rel = path.strip_prefix(templates_dir).unwrap_or(path);
+            selected.insert(rel.to_string_lossy().replace('\\', "/"));
+        }
     }
 
-    // Initialize Tera with all template files
+    Ok(selected)
+}
+
+/// Builds a fresh `Tera` instance over every `.tera` file under
+/// `templates_dir`, with auto-escaping disabled (we're generating code, not
+/// HTML).
+fn load_templates(templates_dir: &Path) -> Result<Tera> {
     let template_pattern = format!("{}/**/*.tera", templates_dir.display());
     let mut tera = Tera::new(&template_pattern)
         .with_context(|| format!("Failed to load templates from {}", templates_dir.display()))?;
-
-    // Disable auto-escaping for code generation
     tera.autoescape_on(vec![]);
+    Ok(tera)
+}
 
-    // Prepare context
+fn render_templates(
+    classes: &[OntologyClass],
+    enumerations: &[Enumeration],
+    templates_dir: &Path,
+    output_dir: &Path,
+    dry_run: bool,
+    verbose: bool,
+    only_templates: Option<&HashSet<String>>,
+) -> Result<()> {
+    let tera = load_templates(templates_dir)?;
+    render_with_tera(&tera, classes, enumerations, output_dir, dry_run, verbose, only_templates)
+}
+
+/// Builds a fresh, cheap `TeraContext` for one render. `classes` and
+/// `enumerations` are the shared extraction cache computed once up front;
+/// inserting them here only serializes into this context's local value
+/// store, so every worker can hold its own context without re-running
+/// extraction.
+fn render_context(classes: &[OntologyClass], enumerations: &[Enumeration]) -> TeraContext {
     let mut context = TeraContext::new();
     context.insert("classes", classes);
-    context.insert("enumerations", &Vec::<String>::new()); // Empty for now
+    context.insert("enumerations", enumerations);
     context.insert("ontology", "specify-domain.ttl");
+    context
+}
+
+/// Renders every (or, if `only_templates` is given, a selected subset of)
+/// template in an already-loaded `Tera` instance. Split out from
+/// `render_templates` so long-lived callers (e.g. `watch`) can keep the same
+/// `Tera` alive across rebuilds instead of re-parsing every template file
+/// each time.
+fn render_with_tera(
+    tera: &Tera,
+    classes: &[OntologyClass],
+    enumerations: &[Enumeration],
+    output_dir: &Path,
+    dry_run: bool,
+    verbose: bool,
+    only_templates: Option<&HashSet<String>>,
+) -> Result<()> {
+    if verbose {
+        println!("\n🎨 Rendering templates to: {}", output_dir.display());
+    }
 
     // Create output directory
     if !dry_run {
@@ -280,17 +1131,38 @@ fn render_templates(
             .with_context(|| format!("Failed to create output directory: {}", output_dir.display()))?;
     }
 
-    // Render each template
-    for template_name in tera.get_template_names() {
+    let mut template_names: Vec<&str> = tera.get_template_names().collect();
+    if let Some(only) = only_templates {
+        template_names.retain(|name| only.contains(*name));
+    }
+
+    // Render every template across a worker pool. Each worker builds its own
+    // context from the shared `classes`/`enumerations` cache (serialized into
+    // that context, not borrowed) rather than re-extracting it, and `tera`
+    // itself is read-only during render.
+    let mut rendered: Vec<(String, String)> = template_names
+        .par_iter()
+        .map(|template_name| -> Result<(String, String)> {
+            let context = render_context(classes, enumerations);
+            let output = tera
+                .render(template_name, &context)
+                .with_context(|| format!("Failed to render template: {}", template_name))?;
+            Ok((template_name.to_string(), output))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Rendering finished in whatever order workers happened to complete;
+    // sort by template name so dry-run/verbose output stays deterministic.
+    rendered.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // Write (or preview) the collected outputs after the parallel phase.
+    for (template_name, output) in rendered {
         if verbose {
             println!("  - Rendering: {}", template_name);
         }
 
-        let output = tera.render(template_name, &context)
-            .with_context(|| format!("Failed to render template: {}", template_name))?;
-
         // Determine output file name (remove .tera extension, add appropriate extension)
-        let temp_path = PathBuf::from(template_name);
+        let temp_path = PathBuf::from(&template_name);
         let output_file = temp_path
             .file_stem()
             .unwrap()
@@ -317,11 +1189,252 @@ fn render_templates(
     Ok(())
 }
 
+/// The default `full` sync: load everything from scratch, extract classes,
+/// render every template, and record a snapshot for later `verify`/
+/// `incremental` runs to compare against.
+fn run_full_sync(ontology_dir: &Path, output_dir: &Path, dry_run: bool, verbose: bool) -> Result<()> {
+    let store = load_ontology(ontology_dir, verbose)?;
+
+    let classes = extract_classes(&store, verbose)?;
+    let enumerations = extract_enumerations(&store, verbose)?;
+
+    if verbose {
+        println!("\n📊 Extracted {} classes", classes.len());
+    }
+
+    let templates_dir = PathBuf::from("templates/ggen");
+    if !templates_dir.exists() {
+        anyhow::bail!("Templates directory not found: {}", templates_dir.display());
+    }
+
+    render_templates(&classes, &enumerations, &templates_dir, output_dir, dry_run, verbose, None)?;
+
+    if !dry_run {
+        // Record this compile so a later `--mode verify`/`incremental` run has a baseline.
+        write_snapshot(&store, output_dir)?;
+        println!("\n✅ Compilation complete! Generated code written to: {}", output_dir.display());
+    } else {
+        println!("\n✅ Dry run complete! Use without --dry-run to write files.");
+    }
+
+    Ok(())
+}
+
+/// `--mode incremental`: diffs the current ontology against the last
+/// recorded snapshot, works out which classes the change actually touched,
+/// and re-renders only the templates whose output depends on those classes.
+/// Falls back to a full sync when there is no snapshot yet to diff against.
+fn run_incremental(ontology_dir: &Path, output_dir: &Path, dry_run: bool, verbose: bool) -> Result<()> {
+    let previous = match load_snapshot(output_dir)? {
+        Some(store) => store,
+        None => {
+            println!("ℹ️  No recorded snapshot yet; compiling fully to establish one.");
+            return run_full_sync(ontology_dir, output_dir, dry_run, verbose);
+        }
+    };
+
+    let store = load_ontology(ontology_dir, verbose)?;
+    let diff = compare_ontology_graphs(&previous, &store)?;
+
+    if diff.isomorphic {
+        println!("⚡ Ontology unchanged since last snapshot - nothing to regenerate.");
+        return Ok(());
+    }
+
+    let classes = extract_classes(&store, verbose)?;
+    let enumerations = extract_enumerations(&store, verbose)?;
+    let changed_iris = diff_to_changed_iris(&diff);
+    let dirty = compute_dirty_classes(&classes, &changed_iris, &previous, &store)?;
+
+    println!("🔥 {} of {} class(es) are dirty:", dirty.len(), classes.len());
+    for class in classes.iter().filter(|c| dirty.contains(&c.iri)) {
+        println!("   - {}", class.name);
+    }
+
+    let templates_dir = PathBuf::from("templates/ggen");
+    if !templates_dir.exists() {
+        anyhow::bail!("Templates directory not found: {}", templates_dir.display());
+    }
+
+    let only = dirty_template_names(&templates_dir, &classes, &dirty)?;
+    render_templates(&classes, &enumerations, &templates_dir, output_dir, dry_run, verbose, Some(&only))?;
+
+    if !dry_run {
+        write_snapshot(&store, output_dir)?;
+        println!("\n✅ Incremental compilation complete! Re-rendered {} template(s).", only.len());
+    } else {
+        println!("\n✅ Dry run complete! Use without --dry-run to write files.");
+    }
+
+    Ok(())
+}
+
+/// `--check`: renders every template in memory and compares the result
+/// byte-for-byte against whatever is already in `output_dir`, without
+/// writing anything. Intended as a CI guard against stale checked-in
+/// generated code, mirroring the `gen --verify` pattern from other build
+/// pipelines.
+fn run_check(ontology_dir: &Path, output_dir: &Path, verbose: bool) -> Result<bool> {
+    println!("🔍 Checking generated code against the ontology (no files will be written)...");
+
+    let store = load_ontology(ontology_dir, verbose)?;
+    let classes = extract_classes(&store, verbose)?;
+    let enumerations = extract_enumerations(&store, verbose)?;
+
+    let templates_dir = PathBuf::from("templates/ggen");
+    if !templates_dir.exists() {
+        anyhow::bail!("Templates directory not found: {}", templates_dir.display());
+    }
+
+    let tera = load_templates(&templates_dir)?;
+    let template_names: Vec<&str> = tera.get_template_names().collect();
+
+    let mut rendered: Vec<(String, String)> = template_names
+        .par_iter()
+        .map(|template_name| -> Result<(String, String)> {
+            let context = render_context(&classes, &enumerations);
+            let output = tera
+                .render(template_name, &context)
+                .with_context(|| format!("Failed to render template: {}", template_name))?;
+            Ok((template_name.to_string(), output))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    rendered.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut in_sync = true;
+    for (template_name, expected) in &rendered {
+        let temp_path = PathBuf::from(template_name);
+        let output_file = temp_path.file_stem().unwrap().to_str().unwrap();
+        let output_path = output_dir.join(output_file);
+
+        let existed = output_path.exists();
+        let actual = if existed {
+            fs::read_to_string(&output_path)
+                .with_context(|| format!("Failed to read {}", output_path.display()))?
+        } else {
+            String::new()
+        };
+
+        if actual == *expected {
+            continue;
+        }
+
+        in_sync = false;
+        println!("\n--- {} ---", output_path.display());
+        if !existed {
+            println!("(missing; would be generated)");
+        }
+
+        let diff = TextDiff::from_lines(&actual, expected);
+        print!(
+            "{}",
+            diff.unified_diff()
+                .header(
+                    &format!("{} (committed)", output_path.display()),
+                    &format!("{} (generated)", output_path.display()),
+                )
+        );
+    }
+
+    if in_sync {
+        println!("\n✅ Generated code is in sync with the ontology.");
+    } else {
+        println!("\n❌ Generated code is out of date. Run `ggen sync` to update it.");
+    }
+
+    Ok(in_sync)
+}
+
+/// Burst of filesystem events within this window are coalesced into a
+/// single rebuild, so an editor's save-storm doesn't trigger redundant
+/// recompiles.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+fn is_ontology_change(event: &notify::Event) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
+        && event.paths.iter().any(|p| is_ontology_file(p))
+}
+
+/// `watch`: runs the load/extract/render pipeline once, then keeps the same
+/// `Store` and `Tera` instance alive and re-runs it whenever a recognized
+/// ontology file (any extension `rdf_format_for_path` maps) under
+/// `ontology_dir` changes, debouncing bursts of events so an editor's
+/// save storm triggers one rebuild instead of several.
+fn run_watch(ontology_dir: &Path, output_dir: &Path, verbose: bool) -> Result<()> {
+    let templates_dir = PathBuf::from("templates/ggen");
+    if !templates_dir.exists() {
+        anyhow::bail!("Templates directory not found: {}", templates_dir.display());
+    }
+
+    let store = Store::new()?;
+    let tera = load_templates(&templates_dir)?;
+
+    let rebuild = |store: &Store, tera: &Tera| -> Result<()> {
+        store.clear()?;
+        load_ontology_into(store, ontology_dir, verbose)?;
+        let classes = extract_classes(store, verbose)?;
+        let enumerations = extract_enumerations(store, verbose)?;
+        render_with_tera(tera, &classes, &enumerations, output_dir, false, verbose, None)?;
+        Ok(())
+    };
+
+    println!("👀 Watching {} for changes (Ctrl+C to stop)", ontology_dir.display());
+    rebuild(&store, &tera)?;
+    write_snapshot(&store, output_dir)?;
+    println!("✅ Initial build complete.\n");
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher =
+        notify::recommended_watcher(tx).context("Failed to create filesystem watcher")?;
+    watcher
+        .watch(ontology_dir, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", ontology_dir.display()))?;
+
+    let mut pending_since: Option<Instant> = None;
+    loop {
+        let timeout = match pending_since {
+            Some(since) => WATCH_DEBOUNCE.saturating_sub(since.elapsed()),
+            None => Duration::from_secs(60 * 60),
+        };
+
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(event)) => {
+                if is_ontology_change(&event) {
+                    if verbose {
+                        println!("   - Change: {:?}", event.paths);
+                    }
+                    pending_since.get_or_insert_with(Instant::now);
+                }
+            }
+            Ok(Err(e)) => {
+                if verbose {
+                    eprintln!("   ⚠️  Watch error: {}", e);
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if let Some(since) = pending_since {
+                    if since.elapsed() >= WATCH_DEBOUNCE {
+                        pending_since = None;
+                        println!("🔁 Change detected, rebuilding...");
+                        match rebuild(&store, &tera).and_then(|_| write_snapshot(&store, output_dir)) {
+                            Ok(()) => println!("✅ Rebuild complete.\n"),
+                            Err(e) => eprintln!("   ❌ Rebuild failed: {}\n", e),
+                        }
+                    }
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Sync { from, to, mode, dry_run, force: _, verbose } => {
+        Commands::Sync { from, to, mode, dry_run, force: _, verbose, check } => {
             let ontology_dir = PathBuf::from(from.unwrap_or_else(|| "schema".to_string()));
             let output_dir = PathBuf::from(to.unwrap_or_else(|| "src/generated".to_string()));
 
@@ -334,32 +1447,36 @@ fn main() -> Result<()> {
             }
             println!();
 
-            // Load ontology
-            let store = load_ontology(&ontology_dir, verbose)?;
-
-            // Extract classes and properties
-            let classes = extract_classes(&store, verbose)?;
-
-            if verbose {
-                println!("\n📊 Extracted {} classes", classes.len());
+            if check {
+                let in_sync = run_check(&ontology_dir, &output_dir, verbose)?;
+                if !in_sync {
+                    std::process::exit(1);
+                }
+                return Ok(());
             }
 
-            // Find templates directory
-            let templates_dir = PathBuf::from("templates/ggen");
-            if !templates_dir.exists() {
-                anyhow::bail!("Templates directory not found: {}", templates_dir.display());
+            match mode.as_str() {
+                "verify" => {
+                    let ok = run_verify(&ontology_dir, &output_dir, verbose)?;
+                    if !ok {
+                        std::process::exit(1);
+                    }
+                    Ok(())
+                }
+                "incremental" => run_incremental(&ontology_dir, &output_dir, dry_run, verbose),
+                _ => run_full_sync(&ontology_dir, &output_dir, dry_run, verbose),
             }
+        }
+        Commands::Watch { from, to, verbose } => {
+            let ontology_dir = PathBuf::from(from.unwrap_or_else(|| "schema".to_string()));
+            let output_dir = PathBuf::from(to.unwrap_or_else(|| "src/generated".to_string()));
 
-            // Render templates
-            render_templates(&classes, &templates_dir, &output_dir, dry_run, verbose)?;
-
-            if !dry_run {
-                println!("\n✅ Compilation complete! Generated code written to: {}", output_dir.display());
-            } else {
-                println!("\n✅ Dry run complete! Use without --dry-run to write files.");
-            }
+            println!("🚀 ggen ontology compiler");
+            println!("   Source: {}", ontology_dir.display());
+            println!("   Output: {}", output_dir.display());
+            println!();
 
-            Ok(())
+            run_watch(&ontology_dir, &output_dir, verbose)
         }
         Commands::Version => {
             println!("ggen 5.0.0");
@@ -368,3 +1485,272 @@ fn main() -> Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_from_turtle(turtle: &str) -> Store {
+        let store = Store::new().expect("in-memory store");
+        store
+            .load_from_reader(RdfFormat::Turtle, turtle.as_bytes())
+            .expect("valid turtle");
+        store
+    }
+
+    #[test]
+    fn isomorphic_graphs_with_renamed_reordered_blanks_compare_equal() {
+        // Same two restrictions as `graph_b`, just in source order and with
+        // whatever blank node ids oxigraph happens to mint for the `[...]`
+        // shorthand - which, being a separate parse, won't match `graph_b`'s.
+        let graph_a = r#"
+            @prefix : <http://example.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            :Person a owl:Class ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 1
+                ] ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :age ;
+                    owl:maxCardinality 1
+                ] .
+        "#;
+        // Same two restrictions, reordered, with different (explicit) blank
+        // node labels.
+        let graph_b = r#"
+            @prefix : <http://example.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            :Person a owl:Class ;
+                rdfs:subClassOf _:r1 ;
+                rdfs:subClassOf _:r2 .
+
+            _:r1 a owl:Restriction ;
+                owl:onProperty :age ;
+                owl:maxCardinality 1 .
+
+            _:r2 a owl:Restriction ;
+                owl:onProperty :name ;
+                owl:minCardinality 1 .
+        "#;
+
+        let old = store_from_turtle(graph_a);
+        let new = store_from_turtle(graph_b);
+
+        let diff = compare_ontology_graphs(&old, &new).expect("comparison succeeds");
+
+        assert!(diff.isomorphic, "graphs differ only by blank-node naming/order");
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn structural_diff_reports_added_and_removed_triples() {
+        let before = r#"
+            @prefix : <http://example.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            :Person a owl:Class ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 1
+                ] .
+        "#;
+        // `:name` goes from required to optional - a real structural change,
+        // not just a relabeling of the restriction blank node.
+        let after = r#"
+            @prefix : <http://example.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            :Person a owl:Class ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 0
+                ] .
+        "#;
+
+        let old = store_from_turtle(before);
+        let new = store_from_turtle(after);
+
+        let diff = compare_ontology_graphs(&old, &new).expect("comparison succeeds");
+
+        assert!(!diff.isomorphic, "minCardinality 1 -> 0 is a real structural change");
+        assert!(!diff.added.is_empty());
+        assert!(!diff.removed.is_empty());
+    }
+
+    #[test]
+    fn colliding_restrictions_on_the_same_property_still_compare_equal() {
+        // Two restrictions on the *same* property with the *same* cardinality
+        // are genuinely indistinguishable by structure alone - both blank
+        // nodes tie into one collision group in round 0, unlike the fixtures
+        // above (where `owl:onProperty` already differs and breaks the tie
+        // immediately).
+        let graph_a = r#"
+            @prefix : <http://example.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            :Person a owl:Class ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 1
+                ] ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 1
+                ] .
+        "#;
+        let graph_b = r#"
+            @prefix : <http://example.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            :Person a owl:Class ;
+                rdfs:subClassOf _:r1 ;
+                rdfs:subClassOf _:r2 .
+
+            _:r1 a owl:Restriction ;
+                owl:onProperty :name ;
+                owl:minCardinality 1 .
+
+            _:r2 a owl:Restriction ;
+                owl:onProperty :name ;
+                owl:minCardinality 1 .
+        "#;
+
+        let old = store_from_turtle(graph_a);
+        let new = store_from_turtle(graph_b);
+
+        let old_triples = collect_graph_triples(&old).expect("old triples");
+        let old_labels = canonical_blank_labels(&old_triples);
+        assert!(
+            !collision_groups(&old_labels).is_empty(),
+            "the two identical restrictions must tie into a real collision group"
+        );
+
+        let diff = compare_ontology_graphs(&old, &new).expect("comparison succeeds");
+
+        assert!(diff.isomorphic, "duplicated, genuinely symmetric restrictions are isomorphic");
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn colliding_restrictions_with_a_real_difference_report_non_isomorphic() {
+        // Same colliding pair of restrictions as above on both sides, but
+        // `before` additionally restricts a second, unrelated property that
+        // `after` drops - a genuine structural difference the collision
+        // group itself must not paper over.
+        let before = r#"
+            @prefix : <http://example.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            :Person a owl:Class ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 1
+                ] ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 1
+                ] ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :age ;
+                    owl:maxCardinality 1
+                ] .
+        "#;
+        let after = r#"
+            @prefix : <http://example.org/> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            :Person a owl:Class ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 1
+                ] ;
+                rdfs:subClassOf [
+                    a owl:Restriction ;
+                    owl:onProperty :name ;
+                    owl:minCardinality 1
+                ] .
+        "#;
+
+        let old = store_from_turtle(before);
+        let new = store_from_turtle(after);
+
+        let diff = compare_ontology_graphs(&old, &new).expect("comparison succeeds");
+
+        assert!(!diff.isomorphic, "dropping the :age restriction is a real structural change");
+        assert!(!diff.removed.is_empty());
+    }
+
+    #[test]
+    fn resolve_via_permutation_finds_a_non_identity_match_and_rejects_mismatches() {
+        // Two blank nodes that tie (same manufactured hash) but are used
+        // asymmetrically: "a" points at :x, "b" points at :y.
+        let triples = vec![
+            RawTriple {
+                subject: NodeKey::Blank("a".to_string()),
+                predicate: "ex:link".to_string(),
+                object: NodeKey::Named("ex:x".to_string()),
+            },
+            RawTriple {
+                subject: NodeKey::Blank("b".to_string()),
+                predicate: "ex:link".to_string(),
+                object: NodeKey::Named("ex:y".to_string()),
+            },
+        ];
+        let mut labels = HashMap::new();
+        labels.insert("a".to_string(), 42u64);
+        labels.insert("b".to_string(), 42u64);
+        let groups = collision_groups(&labels);
+        assert_eq!(groups, vec![vec!["a".to_string(), "b".to_string()]]);
+
+        // A target where the *other* graph's equivalent pair is swapped:
+        // slot 0 points at :y, slot 1 points at :x. The identity assignment
+        // (a -> slot 0, b -> slot 1) renders (slot0, link, x), (slot1, link,
+        // y) - the opposite - and must NOT match; only the swapped
+        // permutation (a -> slot 1, b -> slot 0) reproduces `target`.
+        let slot0 = format!("{}#0", canon_label(42));
+        let slot1 = format!("{}#1", canon_label(42));
+        let mut target = vec![
+            (slot0.clone(), "ex:link".to_string(), "ex:y".to_string()),
+            (slot1.clone(), "ex:link".to_string(), "ex:x".to_string()),
+        ];
+        target.sort();
+
+        assert!(
+            resolve_via_permutation(&triples, &labels, &groups, &target),
+            "the swapped permutation must reproduce the target exactly"
+        );
+
+        // A target no permutation of {a, b} can ever reproduce (both slots
+        // point at :x).
+        let mut unreachable_target = vec![
+            (slot0, "ex:link".to_string(), "ex:x".to_string()),
+            (slot1, "ex:link".to_string(), "ex:x".to_string()),
+        ];
+        unreachable_target.sort();
+
+        assert!(!resolve_via_permutation(&triples, &labels, &groups, &unreachable_target));
+    }
+}